@@ -1,8 +1,8 @@
 use std::error::Error;
 use std::fs;
-use toph::{component::*, tag::*, Node};
+use toph::{component::*, tag::*, text, Node};
 
-fn button(text: &str) -> Node {
+fn button(label: &str) -> Node {
     let css = r#"
         button {
             padding: 0.5rem 1.25rem;
@@ -10,7 +10,7 @@ fn button(text: &str) -> Node {
             border-radius: 0.25rem;
         }
     "#;
-    button_.set(t_(text)).stylesheet(css)
+    button_.set(text(label)).stylesheet(css)
 }
 fn header() -> Node {
     let nav_elements = [
@@ -21,7 +21,7 @@ fn header() -> Node {
         "Questions?",
     ];
 
-    let li_items = nav_elements.into_iter().map(|e| li_.set(a_.set(t_(e))));
+    let li_items = nav_elements.into_iter().map(|e| li_.set(a_.set(text(e))));
     let nav = ul_.set(li_items);
     let login = button("Login");
     let cta = button("Get Started");
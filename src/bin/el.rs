@@ -1,5 +1,5 @@
 use std::fs;
-use toph::{attr, layout::*, tag::*, Node};
+use toph::{attr, component::*, tag::*, text, Node};
 
 fn stub() -> Node {
     let css = ".stub { width: 50px; height: 50px; background-color: black }";
@@ -10,9 +10,9 @@ fn main() {
     let mut html: Node = [
         doctype_,
         html_.set([
-            head_.set(title_.set("Every Layout")),
+            head_.set(title_.set(text("Every Layout"))),
             body_.set([
-                h1_.set("Stack"),
+                h1_.set(text("Stack")),
                 stack(
                     5,
                     [
@@ -21,9 +21,9 @@ fn main() {
                         padded(1, stack(6, [stub(), stub(), stub()])),
                     ],
                 ),
-                h1_.set("Center"),
+                h1_.set(text("Center")),
                 center([stub()]),
-                h1_.set("Cluster"),
+                h1_.set(text("Cluster")),
                 cluster(
                     5,
                     [
@@ -37,13 +37,13 @@ fn main() {
                         stub(),
                     ],
                 ),
-                h1_.set("Switcher"),
+                h1_.set(text("Switcher")),
                 switcher(4, 60, [stub(), stub(), stub(), stub()]),
-                h1_.set("Cover"),
+                h1_.set(text("Cover")),
                 cover(stub(), None, None, Some(50)),
                 cover(stub(), Some(stub()), None, None),
                 cover(stub(), Some(stub()), Some(stub()), None),
-                h1_.set("Fluid Grid"),
+                h1_.set(text("Fluid Grid")),
                 fluid_grid(
                     10,
                     1,
@@ -59,14 +59,14 @@ fn main() {
                         stub(),
                     ],
                 ),
-                h1_.set("Frame"),
+                h1_.set(text("Frame")),
                 frame(
                     (3, 4),
                     img_.with(
                         attr![src="https://img.freepik.com/free-photo/painting-mountain-lake-with-mountain-background_188544-9126.jpg"]
                         )
                     ).with(attr![style="width: 400px;"]),
-                h1_.set("Manual SVG"),
+                h1_.set(text("Manual SVG")),
                 svg_.with(attr![width="32", height="32", viewBox="0 0 32 32"])
                     .set(custom_("path")
                          .with(attr![
@@ -2,7 +2,7 @@
 //!
 //! Sources: <https://every-layout.dev>
 
-use crate::{attr, tag::*, Node};
+use crate::{attr, encode, raw_text, tag::*, text, Node};
 use std::fmt::Display;
 
 impl From<u8> for ModularSpacing {
@@ -317,3 +317,387 @@ where
 pub fn css_reset() -> Node {
     span_.stylesheet(include_str!("css/reset.css"))
 }
+
+/// A named color scheme for [`themed`]
+pub enum Theme {
+    /// Dark text on a light background
+    Light,
+    /// Light text on a dark background
+    Dark,
+    /// Follows the visitor's OS-level `prefers-color-scheme` setting, falling back to [`Theme::Light`]
+    Auto,
+}
+
+impl Theme {
+    fn class(&self) -> &'static str {
+        match self {
+            Theme::Light => "t-theme-light",
+            Theme::Dark => "t-theme-dark",
+            Theme::Auto => "t-theme-auto",
+        }
+    }
+}
+
+/// Scopes `child` to a color theme by defining the `--t-color-bg`, `--t-color-fg`,
+/// `--t-color-accent` and `--t-color-border` custom properties that [`css_reset`] (and any
+/// component's stylesheet) can read with a fallback, e.g. `var(--t-color-bg, inherit)`; the
+/// wrapper itself is painted with `--t-color-bg`/`--t-color-fg` directly, so the themed region
+/// always shows the right colors regardless of where it sits in the page.
+///
+/// Wrap the whole page in this to switch every such component over to `theme`'s palette at once,
+/// the way rustdoc swaps its ayu/dark/light variable sets. Custom properties only inherit
+/// downward, though: if `themed` doesn't wrap the actual document root, [`css_reset`]'s own
+/// `html { background-color: var(--t-color-bg, inherit) }` rule is outside the themed subtree and
+/// won't pick up the theme; it's the wrapper's own background that will show.
+pub fn themed(theme: Theme, child: Node) -> Node {
+    div_.with(attr![class = theme.class()])
+        .set([child])
+        .stylesheet(include_str!("css/theme.css"))
+}
+
+/// The numbering scheme for [`numbered`]'s generated content, mirroring the matching CSS
+/// `list-style-type` keyword.
+pub enum Counter {
+    /// `1`, `2`, `3`, ...
+    Decimal,
+    /// `a`, `b`, `c`, ...
+    LowerAlpha,
+    /// `A`, `B`, `C`, ...
+    UpperAlpha,
+    /// `i`, `ii`, `iii`, ...
+    LowerRoman,
+    /// `I`, `II`, `III`, ...
+    UpperRoman,
+}
+
+impl Counter {
+    fn keyword(&self) -> &'static str {
+        match self {
+            Counter::Decimal => "decimal",
+            Counter::LowerAlpha => "lower-alpha",
+            Counter::UpperAlpha => "upper-alpha",
+            Counter::LowerRoman => "lower-roman",
+            Counter::UpperRoman => "upper-roman",
+        }
+    }
+}
+
+/// A container that numbers its direct children with CSS-generated content instead of `<ol>`
+/// markup, in `style`'s numbering scheme.
+///
+/// Composes with [`stack`]/[`cluster`] like a plain `<div>` would; unlike an `<ol>`, the generated
+/// number is pure presentation, so it's well suited to stepped instructions or legal-style nested
+/// numbering (`1`, `1.1`, `1.2`) built by nesting a `numbered` inside another's child.
+///
+/// The CSS counter backing the numbering is named with a random per-instance suffix, the same
+/// scheme [`crate::node::CSSVariableMap`] uses for variable names, so a nested `numbered` never
+/// clobbers its parent's count.
+pub fn numbered<I, E>(style: Counter, child: I) -> Node
+where
+    I: IntoIterator<Item = E>,
+    E: Into<Node>,
+{
+    let name = format!("t-num-{}", fastrand::u32(0..u32::MAX));
+    // The `::before` rule's `content` value is CSS functional notation (`counter(name, style)`),
+    // not a runtime string, so it's appended via `raw_text` instead of `Node::rule`: `rule()`
+    // runs every declaration value through `encode::css`, which backslash-escapes the parens and
+    // comma a `counter()` call needs and would render it as an invalid declaration.
+    let counters = style_
+        .rule(&format!(".{name}"), [("counter-reset", name.clone())])
+        .rule(&format!(".{name} > *"), [("counter-increment", name.clone())])
+        .set(std::iter::once(raw_text(format!(
+            ".{name} > *::before {{ content: counter({}, {}); }}",
+            name,
+            style.keyword()
+        ))));
+
+    let numbered = div_
+        .with(attr![class = format!("t-numbered {name}")])
+        .set(child)
+        .stylesheet(include_str!("css/numbered.css"));
+
+    // `counters` is a sibling of `numbered`, not one of its children, so the `.{name} > *`
+    // rules above only ever match the caller's own items, never the `<style>` tag itself.
+    [numbered, counters].into()
+}
+
+/// The semantic class a highlighted token belongs to. [`code_block`]'s bundled stylesheet gives
+/// each of these a distinct color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HlClass {
+    /// A reserved word of the language, e.g. `fn`, `if`.
+    Keyword,
+    /// An identifier: a variable, type, or function name.
+    Ident,
+    /// A quoted string or character literal.
+    Str,
+    /// A numeric literal.
+    Num,
+    /// A line or block comment.
+    Comment,
+    /// Structural punctuation, e.g. `( ) { } [ ] , ; .`.
+    Punct,
+    /// An operator, e.g. `+ - * / = == && |`.
+    Op,
+}
+
+impl HlClass {
+    fn css_class(self) -> &'static str {
+        match self {
+            HlClass::Keyword => "t-hl-kw",
+            HlClass::Ident => "t-hl-ident",
+            HlClass::Str => "t-hl-lit-str",
+            HlClass::Num => "t-hl-lit-num",
+            HlClass::Comment => "t-hl-comment",
+            HlClass::Punct => "t-hl-punct",
+            HlClass::Op => "t-hl-op",
+        }
+    }
+}
+
+/// Tokenizes source code for [`code_block`].
+///
+/// Implement this to add highlighting support for a language [`CLike`] doesn't tokenize well.
+/// `tokenize` splits `source` into a contiguous, gap-free sequence of spans: a `Some(class)` span
+/// becomes a `<span>` carrying `class`'s CSS class, while a `None` span (typically whitespace) is
+/// left as plain, unwrapped text.
+pub trait Highlighter {
+    /// Splits `source` into `(class, text)` spans covering the whole of `source`, in order.
+    fn tokenize<'s>(&self, source: &'s str) -> Vec<(Option<HlClass>, &'s str)>;
+}
+
+/// A generic, C-family [`Highlighter`]: `//` and `/* */` comments, single/double-quoted literals
+/// with backslash escapes, `0`-`9`-led numeric literals, and a configurable keyword list. Good
+/// enough for most curly-brace languages; [`RUST`] is an instance of this for Rust's keywords.
+pub struct CLike(pub &'static [&'static str]);
+
+/// Rust's keywords, as a [`CLike`] highlighter
+pub const RUST: CLike = CLike(&[
+    "as", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn",
+    "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe",
+    "use", "where", "while", "async", "await",
+]);
+
+// Operators are grouped into one span per contiguous run (e.g. `==`, `&&`); everything else
+// that isn't alphanumeric, whitespace, a quote or a comment marker is punctuation.
+const OP_CHARS: &str = "+-*/%=<>!&|^~";
+
+impl Highlighter for CLike {
+    fn tokenize<'s>(&self, source: &'s str) -> Vec<(Option<HlClass>, &'s str)> {
+        let mut tokens = Vec::new();
+        let mut i = 0;
+
+        while i < source.len() {
+            let rest = &source[i..];
+            let c = rest.chars().next().expect("i < source.len()");
+
+            if c == '/' && rest.starts_with("//") {
+                let end = rest.find('\n').unwrap_or(rest.len());
+                tokens.push((Some(HlClass::Comment), &rest[..end]));
+                i += end;
+            } else if c == '/' && rest.starts_with("/*") {
+                let end = rest.find("*/").map(|p| p + 2).unwrap_or(rest.len());
+                tokens.push((Some(HlClass::Comment), &rest[..end]));
+                i += end;
+            } else if c == '"' {
+                let end = string_end(rest, '"');
+                tokens.push((Some(HlClass::Str), &rest[..end]));
+                i += end;
+            } else if c == '\'' {
+                // Either a lifetime (`'a`) or a char literal (`'a'`, `'\n'`); lifetimes read as
+                // identifiers since they name a binding, same as any other identifier.
+                let ident_len = rest[1..]
+                    .chars()
+                    .take_while(|c| c.is_alphanumeric() || *c == '_')
+                    .map(char::len_utf8)
+                    .sum::<usize>();
+                let after_ident = &rest[1 + ident_len..];
+                if ident_len > 0 && !after_ident.starts_with('\'') {
+                    tokens.push((Some(HlClass::Ident), &rest[..1 + ident_len]));
+                    i += 1 + ident_len;
+                } else {
+                    let end = string_end(rest, '\'');
+                    tokens.push((Some(HlClass::Str), &rest[..end]));
+                    i += end;
+                }
+            } else if c.is_ascii_digit() {
+                let end = rest
+                    .char_indices()
+                    .take_while(|(_, c)| c.is_alphanumeric() || *c == '_' || *c == '.')
+                    .last()
+                    .map(|(idx, c)| idx + c.len_utf8())
+                    .unwrap_or(0);
+                tokens.push((Some(HlClass::Num), &rest[..end]));
+                i += end;
+            } else if c.is_alphabetic() || c == '_' {
+                let end = rest
+                    .char_indices()
+                    .take_while(|(_, c)| c.is_alphanumeric() || *c == '_')
+                    .last()
+                    .map(|(idx, c)| idx + c.len_utf8())
+                    .unwrap_or(0);
+                let word = &rest[..end];
+                let class = if self.0.contains(&word) {
+                    HlClass::Keyword
+                } else {
+                    HlClass::Ident
+                };
+                tokens.push((Some(class), word));
+                i += end;
+            } else if c.is_whitespace() {
+                let end = rest
+                    .char_indices()
+                    .take_while(|(_, c)| c.is_whitespace())
+                    .last()
+                    .map(|(idx, c)| idx + c.len_utf8())
+                    .unwrap_or(0);
+                tokens.push((None, &rest[..end]));
+                i += end;
+            } else if OP_CHARS.contains(c) {
+                let end = rest
+                    .char_indices()
+                    .take_while(|(_, c)| OP_CHARS.contains(*c))
+                    .last()
+                    .map(|(idx, c)| idx + c.len_utf8())
+                    .unwrap_or(0);
+                tokens.push((Some(HlClass::Op), &rest[..end]));
+                i += end;
+            } else {
+                let end = rest
+                    .char_indices()
+                    .take_while(|(_, c)| {
+                        !c.is_alphanumeric()
+                            && *c != '_'
+                            && !c.is_whitespace()
+                            && !OP_CHARS.contains(*c)
+                            && *c != '"'
+                            && *c != '\''
+                    })
+                    .last()
+                    .map(|(idx, c)| idx + c.len_utf8())
+                    // `c` itself can fail the test above (e.g. `²` is alphanumeric but neither
+                    // alphabetic nor an ASCII digit); it still becomes one punctuation char.
+                    .unwrap_or(c.len_utf8());
+                tokens.push((Some(HlClass::Punct), &rest[..end]));
+                i += end;
+            }
+        }
+
+        tokens
+    }
+}
+
+// Finds the end of a quoted run (including the closing quote), honoring backslash escapes.
+// Falls back to the rest of the input if the quote is never closed.
+fn string_end(rest: &str, quote: char) -> usize {
+    let mut chars = rest.char_indices().skip(1);
+    while let Some((idx, c)) = chars.next() {
+        if c == '\\' {
+            chars.next();
+        } else if c == quote {
+            return idx + 1;
+        }
+    }
+    rest.len()
+}
+
+fn highlighted_span(class: &'static str, token: &str) -> Node {
+    span_
+        .with(attr![class = class])
+        .set(raw_text(encode::html(token)))
+}
+
+/// A `<pre><code>` block with each lexical span of `source` wrapped in a `<span>` carrying a
+/// semantic highlighting class; see [`HlClass`] for the full set. Plain text (e.g. whitespace) is
+/// left unwrapped. The block's stylesheet is attached via [`Node::stylesheet`], so several
+/// `code_block`s on the same page share a single deduplicated `<style>`.
+///
+/// `highlighter` tokenizes `source`; [`RUST`] is provided out of the box, and implementing
+/// [`Highlighter`] adds support for another language.
+pub fn code_block(highlighter: &impl Highlighter, source: &str) -> Node {
+    let spans = highlighter
+        .tokenize(source)
+        .into_iter()
+        .map(|(class, s)| match class {
+            Some(class) => highlighted_span(class.css_class(), s),
+            None => text(s),
+        });
+
+    pre_
+        .set(code_.set(spans))
+        .stylesheet(include_str!("css/code_block.css"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_distinguishes_keywords_from_identifiers() {
+        assert_eq!(
+            RUST.tokenize("let x"),
+            vec![
+                (Some(HlClass::Keyword), "let"),
+                (None, " "),
+                (Some(HlClass::Ident), "x"),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_honors_backslash_escapes_in_strings() {
+        assert_eq!(
+            RUST.tokenize(r#""a\"b""#),
+            vec![(Some(HlClass::Str), r#""a\"b""#)]
+        );
+    }
+
+    #[test]
+    fn tokenize_distinguishes_lifetimes_from_char_literals() {
+        assert_eq!(
+            RUST.tokenize("'a 'a'"),
+            vec![
+                (Some(HlClass::Ident), "'a"),
+                (None, " "),
+                (Some(HlClass::Str), "'a'"),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_reads_line_and_block_comments() {
+        assert_eq!(
+            RUST.tokenize("// line\n/* block */"),
+            vec![
+                (Some(HlClass::Comment), "// line"),
+                (None, "\n"),
+                (Some(HlClass::Comment), "/* block */"),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_reads_numeric_literals() {
+        assert_eq!(
+            RUST.tokenize("1_000 3.14"),
+            vec![
+                (Some(HlClass::Num), "1_000"),
+                (None, " "),
+                (Some(HlClass::Num), "3.14"),
+            ]
+        );
+    }
+
+    #[test]
+    fn numbered_emits_an_unescaped_counter_function() {
+        let mut html = numbered(Counter::Decimal, [p_.set(text("one"))]);
+        let rendered = html.write_to_string(false);
+
+        // The `counter(...)` call must survive as CSS functional notation: if its value were run
+        // through `encode::css` (as a plain `Node::rule` declaration would be), the parens and
+        // comma would come out backslash-escaped and no number would ever display.
+        assert!(rendered.contains("content: counter("), "{rendered}");
+        assert!(!rendered.contains(r"\28"), "{rendered}");
+    }
+}
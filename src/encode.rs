@@ -33,6 +33,72 @@ pub fn attr(value: &str) -> String {
     value.replace('"', "&quot;")
 }
 
+// Escapes `input` for the JavaScript-string context, so it can be interpolated into a
+// single/double-quoted string literal without breaking out of the string, closing the enclosing
+// `<script>` tag, or being interpreted as the start of a new statement.
+pub fn js(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '<' => escaped.push_str("\\x3C"),
+            '>' => escaped.push_str("\\x3E"),
+            '&' => escaped.push_str("\\x26"),
+            '\'' => escaped.push_str("\\x27"),
+            '"' => escaped.push_str("\\x22"),
+            '/' => escaped.push_str("\\x2F"),
+            '`' => escaped.push_str("\\x60"),
+            '\n' => escaped.push_str("\\x0A"),
+            '\r' => escaped.push_str("\\x0D"),
+            '\t' => escaped.push_str("\\x09"),
+            '\u{2028}' => escaped.push_str("\\u2028"),
+            '\u{2029}' => escaped.push_str("\\u2029"),
+            c => {
+                let mut buf = [0; 4];
+                escaped.push_str(c.encode_utf8(&mut buf));
+            }
+        }
+    }
+    escaped
+}
+
+// Escapes `input` for the CSS string/identifier context: every character that isn't an ASCII
+// letter or digit is backslash-hex escaped, so a value can't terminate a declaration early or
+// inject a `</style>` close tag.
+pub fn css(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        if c.is_ascii_alphanumeric() {
+            escaped.push(c);
+        } else {
+            escaped.push('\\');
+            escaped.push_str(&format!("{:x} ", c as u32));
+        }
+    }
+    escaped
+}
+
+// Percent-encodes everything outside of the URL-safe character set. Returns `None` when the
+// value should be dropped instead of rendered (currently unused, but lets callers reject values
+// without changing this function's signature)
+pub fn url(value: &str) -> Option<String> {
+    const SAFE: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_.~:/?#[]@!$&'()*+,;=%";
+
+    if value.bytes().all(|b| SAFE.contains(&b)) {
+        return Some(value.to_string());
+    }
+
+    let mut escaped = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if SAFE.contains(&byte) {
+            escaped.push(byte as char);
+        } else {
+            escaped.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    Some(escaped)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -48,4 +114,27 @@ mod tests {
     fn encoding_attributes() {
         assert_eq!(attr("some\"attribute"), "some&quot;attribute");
     }
+
+    #[test]
+    fn encoding_js_prevents_tag_breakout() {
+        assert_eq!(
+            js("</script><script>alert(1)</script>"),
+            "\\x3C\\x2Fscript\\x3E\\x3Cscript\\x3Ealert(1)\\x3C\\x2Fscript\\x3E"
+        );
+    }
+
+    #[test]
+    fn encoding_js_escapes_quotes_and_line_separators() {
+        assert_eq!(js("it's \"ok\"\u{2028}"), "it\\x27s \\x22ok\\x22\\u2028");
+    }
+
+    #[test]
+    fn encoding_js_escapes_newlines_and_tabs() {
+        assert_eq!(js("a\nb\rc\td"), "a\\x0Ab\\x0Dc\\x09d");
+    }
+
+    #[test]
+    fn encoding_css_escapes_non_alphanumerics() {
+        assert_eq!(css("red; } </style>"), "red\\3b \\20 \\7d \\20 \\3c \\2f style\\3e ");
+    }
 }
@@ -1,12 +1,17 @@
-mod attribute;
+pub mod component;
 mod encode;
-mod html;
+mod markdown;
 mod node;
+mod parse;
+mod sanitize;
 
 #[doc(hidden)]
-pub use attribute::Attribute;
+pub use node::AttributeMap;
 
-pub use node::{raw_text, text, Element, Fragment, Node, Text};
+pub use markdown::{markdown, markdown_with, RawHtml};
+pub use node::{raw_text, tag, text, Node};
+pub use parse::ParseError;
+pub use sanitize::{sanitize, sanitized_html, Policy};
 
 #[doc = include_str!("../README.md")]
 #[cfg(doctest)]
@@ -1,16 +1,60 @@
+mod asset;
+mod attribute;
+pub mod tag;
+mod variable;
+mod visitor;
+
 use crate::encode;
-use crate::Attribute;
+use std::collections::BTreeSet;
 use std::fmt::Display;
 
-/// See [`Node`]
+pub use attribute::AttributeMap;
+pub(crate) use asset::Asset;
+pub use variable::CSSVariableMap;
+use visitor::{
+    visit_nodes, CappedHtmlStringWriter, HtmlStringWriter, LimitedHtmlStringWriter, PlainTextWriter,
+};
+
+/// A node in an HTML tree structure
+///
+/// Nodes are created from the constants in the [`tag`] module, and assembled into a tree with
+/// [`Node::with`], [`Node::set`], [`Node::stylesheet`], [`Node::js`] and [`Node::var`].
 #[derive(Debug, Clone)]
-pub struct Element {
-    tag: String,
-    attributes: Vec<Attribute>,
+pub struct Node {
+    tag: &'static str,
+    text: String,
+    attributes: AttributeMap,
+    variables: CSSVariableMap,
+    assets: Vec<Asset>,
     children: Vec<Node>,
+    preformatted: bool,
 }
 
-impl Element {
+impl Node {
+    const fn element(tag: &'static str) -> Node {
+        Node {
+            tag,
+            text: String::new(),
+            attributes: AttributeMap::new(),
+            variables: CSSVariableMap::new(),
+            assets: Vec::new(),
+            children: Vec::new(),
+            preformatted: false,
+        }
+    }
+
+    fn leaf(text: String) -> Node {
+        let mut node = Node::element("");
+        node.text = text;
+        node
+    }
+
+    /// Creates an empty fragment. Appending nodes to this is how a list of sibling nodes is
+    /// represented
+    pub(crate) fn fragment() -> Node {
+        Node::element("")
+    }
+
     fn is_void(&self) -> bool {
         [
             "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source",
@@ -18,126 +62,324 @@ impl Element {
         ]
         .contains(&self.tag.to_lowercase().as_str())
     }
-}
 
-/// See [`Node`]
-#[derive(Debug, Clone)]
-pub struct Text(String);
+    fn is_fragment(&self) -> bool {
+        self.tag.is_empty() && !self.children.is_empty()
+    }
 
-/// See [`Node`]
-#[derive(Debug, Clone)]
-pub struct Fragment(Vec<Node>);
+    fn is_text(&self) -> bool {
+        self.tag.is_empty() && self.children.is_empty()
+    }
 
-/// A node in an HTML tree structure
-///
-/// The [`html`](crate::html!) macro creates instances of this type
-#[derive(Debug, Clone)]
-pub enum Node {
-    /// An HTML element like `<p>` or `<div>`
-    Element(Element),
-    /// Text within an HTML element. This is automatically html-escaped
-    Text(Text),
-    /// Similar to the `Text` variant, except it is included in the final HTML as-is, without
-    /// escaping.
-    RawText(Text),
-    /// A list of HTML nodes.
-    Fragment(Fragment),
-}
+    fn append_child(&mut self, child: Node) {
+        if self.is_text() && !self.text.is_empty() {
+            panic!("cannot add child to text node");
+        }
+        self.children.push(child);
+    }
 
-/// Returns a text [`Node`] whose contents are HTML escaped
-///
-/// See the [`html`](crate::html!) macro for more details
-pub fn text(text: impl Display) -> Node {
-    Node::Text(Text(text.to_string()))
-}
+    /// Adds attributes to this node.
+    ///
+    /// Attribute lists are built with the [`attr`](crate::attr!) macro.
+    pub fn with(mut self, attrs: impl IntoIterator<Item = (&'static str, String, bool)>) -> Node {
+        for (key, value, is_boolean) in attrs {
+            self.attributes.insert(key, &value, is_boolean);
+        }
+        self
+    }
 
-/// Returns a text [`Node`] whose contents are not HTML escaped
-///
-/// See the [`html`](crate::html!) macro for more details
-pub fn raw_text(text: impl Display) -> Node {
-    Node::RawText(Text(text.to_string()))
-}
+    /// Appends children to this node.
+    ///
+    /// Accepts a single [`Node`] or any iterable of things convertible to one.
+    pub fn set<I, E>(mut self, children: I) -> Node
+    where
+        I: IntoIterator<Item = E>,
+        E: Into<Node>,
+    {
+        for child in children {
+            self.append_child(child.into());
+        }
+        self
+    }
 
-enum Tag<'n> {
-    Open(&'n Node),
-    Close(&'n Element),
-}
+    /// Attaches a CSS stylesheet to this node.
+    ///
+    /// Stylesheets attached anywhere in a tree are collected and deduplicated when the tree is
+    /// rendered with [`Node::write_to_string`].
+    pub fn stylesheet(mut self, css: &'static str) -> Node {
+        self.assets.push(Asset::StyleSheet(css));
+        self
+    }
 
-impl Node {
-    #[doc(hidden)]
-    pub fn element(tag: String, attributes: Vec<Attribute>) -> Node {
-        let tag = tag.to_ascii_lowercase();
-        let tag = if tag == "doctype" {
-            String::from("!doctype")
-        } else {
-            tag
-        };
-
-        Node::Element(Element {
-            tag,
-            attributes,
-            children: vec![],
-        })
+    /// Attaches a JavaScript snippet to this node.
+    ///
+    /// Scripts attached anywhere in a tree are collected and deduplicated when the tree is
+    /// rendered with [`Node::write_to_string`].
+    pub fn js(mut self, js: &'static str) -> Node {
+        self.assets.push(Asset::JavaScript(js));
+        self
+    }
+
+    /// Sets a custom CSS variable, scoped to this node, via an inline `style` attribute.
+    pub fn var(mut self, name: &'static str, value: &str) -> Node {
+        self.variables.insert(name, value);
+        self
+    }
+
+    /// Marks this element's subtree as whitespace-significant.
+    ///
+    /// [`Node::write_to_string`] leaves the text inside it untouched when pretty-printing: no
+    /// indentation or extra newlines are added anywhere below this node. [`tag::pre_`],
+    /// [`tag::textarea_`], [`tag::script_`] and [`tag::style_`] are preformatted by default; call
+    /// this on a [`tag::custom_`] element that has the same whitespace-sensitivity (e.g. a
+    /// `<pre>`-like component) to opt it in too.
+    pub const fn preformatted(mut self) -> Node {
+        self.preformatted = true;
+        self
+    }
+
+    /// Appends a `name = "<value>";` assignment to this node's raw text content, with `value`
+    /// encoded for the JavaScript-string context via [`crate::encode::js`]. This is the safe path
+    /// for interpolating runtime data into an inline [`tag::script_`] body: the encoded value
+    /// can't break out of the string, close the `<script>` tag, or inject a new statement.
+    pub fn data(self, name: &str, value: impl Display) -> Node {
+        let statement = format!("{} = \"{}\";\n", name, encode::js(&value.to_string()));
+        self.set(std::iter::once(raw_text(statement)))
     }
 
-    #[doc(hidden)]
-    pub fn fragment() -> Node {
-        Node::Fragment(Fragment(vec![]))
+    /// Appends a `selector { prop: value; ... }` rule to this node's raw text content, with each
+    /// declaration value encoded for the CSS context via [`crate::encode::css`]. This is the safe
+    /// path for interpolating runtime data into an inline [`tag::style_`] body: the encoded value
+    /// can't terminate a declaration or inject a `</style>` close tag. `selector` and declaration
+    /// names are assumed to be trusted, static CSS rather than runtime data.
+    pub fn rule<I>(self, selector: &str, declarations: I) -> Node
+    where
+        I: IntoIterator<Item = (&'static str, String)>,
+    {
+        let mut block = format!("{} {{", selector);
+        for (property, value) in declarations {
+            block.push_str(property);
+            block.push(':');
+            block.push_str(&encode::css(&value));
+            block.push(';');
+        }
+        block.push('}');
+        self.set(std::iter::once(raw_text(block)))
     }
 
-    #[doc(hidden)]
-    pub fn append_child(&mut self, child: Node) {
-        match self {
-            Node::Fragment(Fragment(nodes)) => nodes.push(child),
-            Node::Element(Element { children, .. }) => children.push(child),
-            Node::Text(_) | Node::RawText(_) => panic!("cannot add child to text node"),
+    fn collect_assets(&self) -> (Vec<&'static str>, Vec<&'static str>) {
+        let mut stylesheets = BTreeSet::new();
+        let mut scripts = BTreeSet::new();
+
+        fn walk(node: &Node, stylesheets: &mut BTreeSet<&'static str>, scripts: &mut BTreeSet<&'static str>) {
+            for asset in &node.assets {
+                match asset {
+                    Asset::StyleSheet(css) => {
+                        stylesheets.insert(*css);
+                    }
+                    Asset::JavaScript(js) => {
+                        scripts.insert(*js);
+                    }
+                }
+            }
+            for child in &node.children {
+                walk(child, stylesheets, scripts);
+            }
         }
+
+        walk(self, &mut stylesheets, &mut scripts);
+        (stylesheets.into_iter().collect(), scripts.into_iter().collect())
+    }
+
+    /// Renders this node (and its descendants) to an HTML string.
+    ///
+    /// Stylesheets and scripts attached anywhere in the tree via [`Node::stylesheet`]/[`Node::js`]
+    /// are collected, deduplicated, and emitted once ahead of the tree itself.
+    ///
+    /// When `pretty` is `true`, the output is indented for readability.
+    pub fn write_to_string(&mut self, pretty: bool) -> String {
+        let mut out = String::new();
+
+        let (stylesheets, scripts) = self.collect_assets();
+        for css in stylesheets {
+            out.push_str("<style>");
+            out.push_str(css);
+            out.push_str("</style>");
+        }
+        for js in scripts {
+            out.push_str("<script>");
+            out.push_str(js);
+            out.push_str("</script>");
+        }
+
+        let writer = HtmlStringWriter::new(&mut out, pretty);
+        visit_nodes(self, writer).expect("writing to a String never fails");
+        out
+    }
+
+    /// Renders this node (and its descendants) to an HTML string, stopping once `max_len` bytes
+    /// of *text content* have been written.
+    ///
+    /// Unlike naively truncating the output of [`Node::write_to_string`], this always produces
+    /// well-formed HTML: every element still open when the budget is reached is closed before
+    /// returning. Only text (and `raw_text`) content counts against `max_len` — tag names and
+    /// attributes are free. Useful for generating preview cards and search result excerpts from
+    /// larger documents.
+    pub fn write_to_string_truncated(&mut self, pretty: bool, max_len: usize) -> String {
+        let mut out = String::new();
+
+        let (stylesheets, scripts) = self.collect_assets();
+        for css in stylesheets {
+            out.push_str("<style>");
+            out.push_str(css);
+            out.push_str("</style>");
+        }
+        for js in scripts {
+            out.push_str("<script>");
+            out.push_str(js);
+            out.push_str("</script>");
+        }
+
+        let writer = HtmlStringWriter::new_truncated(&mut out, pretty, max_len);
+        // An `Err` here only ever means the writer hit its budget and stopped early; it has
+        // already closed every open tag by that point, so `out` is well-formed regardless.
+        let _ = visit_nodes(self, writer);
+        out
+    }
+
+    /// Renders this node (and its descendants) to an HTML string, stopping once `max_bytes` of
+    /// *visible* text content have been written.
+    ///
+    /// Unlike [`Node::write_to_string_truncated`], an element's opening tag is held back until a
+    /// descendant actually has something to show (text, or a void element); only then is the
+    /// whole ancestor chain flushed. Tags are never charged against the budget. This means a
+    /// subtree that never produces any visible content (e.g. an empty `<div></div>`) is dropped entirely
+    /// instead of leaving behind a skeleton of empty tags. As with the truncated variant, every
+    /// element still open when the budget is reached is closed before returning, so the result
+    /// is always well-formed HTML.
+    pub fn write_to_string_limited(&mut self, max_bytes: usize) -> String {
+        let mut out = String::new();
+
+        let (stylesheets, scripts) = self.collect_assets();
+        for css in stylesheets {
+            out.push_str("<style>");
+            out.push_str(css);
+            out.push_str("</style>");
+        }
+        for js in scripts {
+            out.push_str("<script>");
+            out.push_str(js);
+            out.push_str("</script>");
+        }
+
+        let writer = LimitedHtmlStringWriter::new(&mut out, max_bytes);
+        // An `Err` here only ever means the writer hit its budget and stopped early; it has
+        // already closed every open tag by that point, so `out` is well-formed regardless.
+        let _ = visit_nodes(self, writer);
+        out
+    }
+
+    /// Renders this node (and its descendants) to an HTML string capped at `max_bytes` of *total*
+    /// output, returning the string along with whether it had to be truncated.
+    ///
+    /// Unlike [`Node::write_to_string_truncated`] and [`Node::write_to_string_limited`], which
+    /// only charge text content against their budget, every byte of the rendered tag names and
+    /// attributes counts here too. As with those methods, every element still open when the
+    /// budget is reached is closed before returning, so the result is always well-formed HTML.
+    pub fn write_to_string_capped(&mut self, max_bytes: usize) -> (String, bool) {
+        let mut out = String::new();
+
+        let (stylesheets, scripts) = self.collect_assets();
+        for css in stylesheets {
+            out.push_str("<style>");
+            out.push_str(css);
+            out.push_str("</style>");
+        }
+        for js in scripts {
+            out.push_str("<script>");
+            out.push_str(js);
+            out.push_str("</script>");
+        }
+
+        let writer = CappedHtmlStringWriter::new(&mut out, max_bytes);
+        let writer = visit_nodes(self, writer).expect("writing to a String never fails");
+        let truncated = writer.truncated();
+        (out, truncated)
+    }
+
+    /// Renders this node (and its descendants) to word-wrapped plain text, wrapping lines at
+    /// `width` columns.
+    ///
+    /// Useful for generating a `text/plain` alternative to accompany an HTML email, or for
+    /// terminal output. `p`, `div`, headings and `blockquote` start a new paragraph (a blank
+    /// line); `li`, `ul` and `ol` only start a new line; everything else is treated as inline. An
+    /// `a` renders as its visible text followed by its `href` in brackets.
+    pub fn write_text(&mut self, width: usize) -> String {
+        let mut out = String::new();
+        let writer = PlainTextWriter::new(&mut out, width);
+        visit_nodes(self, writer).expect("writing to a String never fails");
+        out
+    }
+
+    /// Parses `html` into a [`Node`] tree, the inverse of [`Node::write_to_string`].
+    ///
+    /// Every tag and attribute name is kept, even ones this crate has no built-in constant for
+    /// (unlike [`crate::sanitized_html`], which only keeps a fixed allowlist). HTML5's optional
+    /// closing tags (e.g. a `<li>` that implicitly closes the previous one) are handled, as are
+    /// named, decimal and hex character references.
+    ///
+    /// `html` should come from a trusted source. Each distinct tag and attribute name encountered
+    /// is interned into a process-global cache for the lifetime of the program (so that, like the
+    /// `tag` module's constants, a [`Node`]'s tag and attribute keys can stay plain `&'static
+    /// str`), and that cache is never freed. Parsing attacker-controlled HTML with many distinct,
+    /// unique tag/attribute names (e.g. randomly generated ones) is an unbounded memory leak.
+    pub fn parse(html: &str) -> Result<Node, crate::ParseError> {
+        crate::parse::parse(html)
     }
 }
 
+enum Tag<'n> {
+    Open(&'n Node),
+    Close(&'static str),
+}
+
 impl Display for Node {
     /// Converts the Node to an HTML string
+    ///
+    /// Unlike [`Node::write_to_string`], this does not collect or emit stylesheets/scripts
+    /// attached to the tree.
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // Serialization is done by traversing the tree in a depth-first manner.
-        // Open tags are serialized on the way down, closing tags are serialized on the way up
         let mut visit_later = vec![Tag::Open(self)];
 
         while let Some(t) = visit_later.pop() {
             match t {
-                Tag::Open(Node::Text(Text(s))) => {
-                    write!(f, "{}", encode::html(s))?;
+                Tag::Open(n) if n.is_text() => {
+                    write!(f, "{}", n.text)?;
                 }
-                Tag::Open(Node::RawText(Text(s))) => {
-                    write!(f, "{s}")?;
+                Tag::Open(n) if n.is_fragment() => {
+                    for child in n.children.iter().rev() {
+                        visit_later.push(Tag::Open(child));
+                    }
                 }
-                Tag::Open(Node::Element(el)) => {
-                    let attributes = el
-                        .attributes
-                        .iter()
-                        .map(|a| a.to_string())
-                        .collect::<Vec<_>>()
-                        .join("");
-
-                    write!(f, "<{}{}>", el.tag.replace('_', "-"), attributes)?;
+                Tag::Open(n) => {
+                    write!(f, "<{}{}", n.tag, n.attributes)?;
+                    if !n.variables.is_empty() {
+                        write!(f, " style=\"{}\"", n.variables)?;
+                    }
+                    write!(f, ">")?;
 
-                    if el.is_void() {
+                    if n.is_void() {
                         continue;
                     }
 
-                    // re-visit this node after its children have been visited
-                    visit_later.push(Tag::Close(el));
+                    visit_later.push(Tag::Close(n.tag));
 
-                    for child in el.children.iter().rev() {
-                        visit_later.push(Tag::Open(child));
-                    }
-                }
-                Tag::Open(Node::Fragment(fragment)) => {
-                    for child in fragment.0.iter().rev() {
+                    for child in n.children.iter().rev() {
                         visit_later.push(Tag::Open(child));
                     }
                 }
-                Tag::Close(el) => {
-                    write!(f, "</{}>", el.tag.replace('_', "-"))?;
+                Tag::Close(tag) => {
+                    write!(f, "</{}>", tag)?;
                 }
             }
         }
@@ -146,17 +388,251 @@ impl Display for Node {
     }
 }
 
+/// Returns a text [`Node`] whose contents are HTML escaped
+pub fn text(text: impl Display) -> Node {
+    Node::leaf(encode::html(&text.to_string()))
+}
+
+/// Returns a text [`Node`] whose contents are not HTML escaped
+pub fn raw_text(text: impl Display) -> Node {
+    Node::leaf(text.to_string())
+}
+
+impl IntoIterator for Node {
+    type Item = Node;
+    type IntoIter = std::iter::Once<Node>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        std::iter::once(self)
+    }
+}
+
+impl From<Option<Node>> for Node {
+    fn from(value: Option<Node>) -> Self {
+        value.unwrap_or_else(Node::fragment)
+    }
+}
+
+impl<const N: usize> From<[Node; N]> for Node {
+    fn from(value: [Node; N]) -> Self {
+        let mut fragment = Node::fragment();
+        for child in value {
+            fragment.append_child(child);
+        }
+        fragment
+    }
+}
+
+impl From<Vec<Node>> for Node {
+    fn from(value: Vec<Node>) -> Self {
+        let mut fragment = Node::fragment();
+        for child in value {
+            fragment.append_child(child);
+        }
+        fragment
+    }
+}
+
 impl From<Node> for String {
     fn from(value: Node) -> Self {
         value.to_string()
     }
 }
 
-impl<T> From<T> for Node
-where
-    T: IntoIterator<Item = Node>,
-{
-    fn from(value: T) -> Self {
-        Node::Fragment(Fragment(value.into_iter().collect::<Vec<Node>>()))
+#[cfg(test)]
+mod tests {
+    use super::Node;
+    use crate::tag::*;
+    use crate::{attr, text};
+
+    #[test]
+    fn truncation_closes_every_open_tag() {
+        let mut html = div_.set(p_.set(text("hello world")));
+
+        assert_eq!(
+            html.write_to_string_truncated(false, 5),
+            "<div><p>hello</p></div>"
+        );
+    }
+
+    #[test]
+    fn truncation_does_not_split_multi_byte_characters() {
+        let mut html = p_.set(text("héllo"));
+
+        // 'h' is 1 byte, 'é' is 2 bytes; a budget of 2 falls in the middle of 'é'
+        assert_eq!(html.write_to_string_truncated(false, 2), "<p>h</p>");
+    }
+
+    #[test]
+    fn truncation_within_budget_is_unaffected() {
+        let mut html = p_.set(text("hi"));
+
+        assert_eq!(
+            html.write_to_string_truncated(false, 100),
+            html.clone().write_to_string(false)
+        );
+    }
+
+    #[test]
+    fn limited_closes_every_open_tag() {
+        let mut html = div_.set(p_.set(text("hello world")));
+
+        assert_eq!(
+            html.write_to_string_limited(5),
+            "<div><p>hello</p></div>"
+        );
+    }
+
+    #[test]
+    fn limited_drops_subtrees_with_no_visible_content() {
+        let mut html = div_.set([p_.set(text("hi")), div_]);
+
+        assert_eq!(html.write_to_string_limited(100), "<div><p>hi</p></div>");
+    }
+
+    #[test]
+    fn limited_emits_nothing_for_a_textless_tree() {
+        let mut html = div_.set(div_.set(div_));
+
+        assert_eq!(html.write_to_string_limited(100), "");
+    }
+
+    #[test]
+    fn limited_within_budget_is_unaffected() {
+        let mut html = p_.set(text("hi"));
+
+        assert_eq!(
+            html.write_to_string_limited(100),
+            html.clone().write_to_string(false)
+        );
+    }
+
+    #[test]
+    fn capped_closes_every_open_tag() {
+        let mut html = div_.set(p_.set(text("hello world")));
+
+        let (out, truncated) = html.write_to_string_capped(10);
+        assert_eq!(out, "<div><p>he</p></div>");
+        assert!(truncated);
+    }
+
+    #[test]
+    fn capped_does_not_split_multi_byte_characters() {
+        let mut html = p_.set(text("héllo"));
+
+        // the `<p>` tag itself eats 3 of the 4 byte budget; 'é' is 2 bytes, so only 'h' fits
+        let (out, truncated) = html.write_to_string_capped(4);
+        assert_eq!(out, "<p>h</p>");
+        assert!(truncated);
+    }
+
+    #[test]
+    fn capped_counts_tags_and_attributes_against_the_budget() {
+        let mut html = p_.with(attr![class = "greeting"]).set(text("hi"));
+
+        // "<p class=\"greeting\">" alone is already 20 bytes, more than the budget
+        let (out, truncated) = html.write_to_string_capped(10);
+        assert_eq!(out, "");
+        assert!(truncated);
+    }
+
+    #[test]
+    fn capped_within_budget_is_unaffected() {
+        let mut html = p_.set(text("hi"));
+
+        let (out, truncated) = html.write_to_string_capped(100);
+        assert_eq!(out, html.clone().write_to_string(false));
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn data_escapes_values_for_the_js_string_context() {
+        let mut html = script_.data("window.name", "</script><b>\"hi\"</b>");
+
+        assert_eq!(
+            html.write_to_string(false),
+            "<script>window.name = \"\\x3C\\x2Fscript\\x3E\\x3Cb\\x3E\\x22hi\\x22\\x3C\\x2Fb\\x3E\";\n</script>"
+        );
+    }
+
+    #[test]
+    fn rule_escapes_values_for_the_css_context() {
+        let mut html = style_.rule(".x", [("content", String::from("</style>"))]);
+
+        assert_eq!(
+            html.write_to_string(false),
+            "<style>.x {content:\\3c \\2f style\\3e ;}</style>"
+        );
+    }
+
+    #[test]
+    fn pretty_printing_does_not_reindent_preformatted_content() {
+        let mut html = div_.set(pre_.set(text("line one\n  line two\nline three")));
+
+        assert_eq!(
+            html.write_to_string(true),
+            "<div>\n  <pre>line one\n  line two\nline three</pre>\n</div>\n"
+        );
+    }
+
+    #[test]
+    fn pretty_printing_indents_structure_around_a_preformatted_child() {
+        let mut html = div_.set([p_.set(text("hi")), pre_.set(text("raw"))]);
+
+        assert_eq!(
+            html.write_to_string(true),
+            "<div>\n  <p>\n    hi\n  </p>\n  <pre>raw</pre>\n</div>\n"
+        );
+    }
+
+    #[test]
+    fn custom_elements_can_opt_into_preformatted_rendering() {
+        let mut html = custom_("x-ascii-art").preformatted().set(text("/\\_/\\\n( o.o )"));
+
+        assert_eq!(
+            html.write_to_string(true),
+            "<x-ascii-art>/\\_/\\\n( o.o )</x-ascii-art>\n"
+        );
+    }
+
+    #[test]
+    fn write_text_wraps_at_the_given_width() {
+        let mut html = p_.set(text("one two three four"));
+
+        assert_eq!(html.write_text(9), "one two\nthree\nfour");
+    }
+
+    #[test]
+    fn write_text_separates_block_elements_with_a_blank_line() {
+        let mut html: Node = [p_.set(text("first")), p_.set(text("second"))].into();
+
+        assert_eq!(html.write_text(80), "first\n\nsecond");
+    }
+
+    #[test]
+    fn write_text_collapses_whitespace_runs() {
+        let mut html = p_.set(text("a   b\n\tc"));
+
+        assert_eq!(html.write_text(80), "a b c");
+    }
+
+    #[test]
+    fn write_text_renders_links_with_their_target_in_brackets() {
+        let mut html = p_.set(a_.with(attr![href = "/about"]).set(text("About us")));
+
+        assert_eq!(html.write_text(80), "About us [/about]");
+    }
+
+    #[test]
+    fn write_text_prefixes_and_indents_list_items() {
+        let mut html = ul_.set([
+            li_.set(text("first")),
+            li_.set(ol_.set([li_.set(text("nested one")), li_.set(text("nested two"))])),
+        ]);
+
+        assert_eq!(
+            html.write_text(80),
+            "- first\n- \n  1. nested one\n  2. nested two"
+        );
     }
 }
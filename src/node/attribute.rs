@@ -40,6 +40,12 @@ const URL_ATTRIBUTES: [&str; 7] = [
     "src",
 ];
 
+// Rejects a `javascript:`-scheme value (ignoring leading whitespace/case) in a `href`/`src`-like
+// attribute, since that scheme executes as code rather than navigating/loading a resource.
+fn is_javascript_scheme(value: &str) -> bool {
+    value.trim_start().to_ascii_lowercase().starts_with("javascript:")
+}
+
 impl AttributeMap {
     /// Create a new attribute map
     pub const fn new() -> Self {
@@ -54,6 +60,11 @@ impl AttributeMap {
         self.regular.entry(key)
     }
 
+    /// Returns the (attribute-encoded) value of `key`, if it was set.
+    pub(crate) fn get(&self, key: &str) -> Option<&str> {
+        self.regular.get(key).map(String::as_str)
+    }
+
     /// Add a new HTML attribute.
     ///
     /// Attributes values are url encoded when necessary. They are alway attribute encoded.
@@ -71,8 +82,10 @@ impl AttributeMap {
             // Boolean attributes are stored verbatim
             self.boolean.insert(key);
         } else {
-            let value = if URL_ATTRIBUTES.contains(&key) {
-                encode::url(&value)
+            let value = if URL_ATTRIBUTES.contains(&key) && is_javascript_scheme(value) {
+                None
+            } else if URL_ATTRIBUTES.contains(&key) {
+                encode::url(value)
             } else {
                 Some(value.into())
             };
@@ -292,6 +305,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn javascript_scheme_url_attributes_are_rejected() {
+        let mut map = AttributeMap::new();
+        map.insert("href", " JavaScript:alert(1)", false);
+
+        assert!(!map.regular.contains_key("href"));
+    }
+
     #[test]
     fn attributes_are_html_attribute_encoded() {
         let mut map = AttributeMap::new();
@@ -5,8 +5,10 @@
 //!
 //! You can also create an HTML element [with a custom tag name](crate::tag::custom_).
 //!
-//! Missing from this module are constants for the `_script` & `_style` elements. JavaScript & CSS
-//! snippets are set using [`Node::js`] and [`Node::stylesheet`] respectively
+//! A whole-tree JavaScript/CSS snippet is set using [`Node::js`] and [`Node::stylesheet`]
+//! respectively. [`script_`] and [`style_`] are for an inline `<script>`/`<style>` element at a
+//! specific point in the tree; combine them with [`Node::data`]/[`Node::rule`] to interpolate
+//! runtime data into one safely.
 use super::*;
 
 /// Creates an HTML Node with a custom tag name.
@@ -33,12 +35,33 @@ macro_rules! impl_tag {
 #[allow(non_upper_case_globals)]
 pub const doctype_: Node = Node::element("!DOCTYPE html");
 
-// script_ & style_ tag constants are omitted from the public API
+/// An inline `<script>` element. Use [`Node::data`] to interpolate runtime data into it safely.
+///
+/// Preformatted by default (see [`Node::preformatted`]): pretty-printing never reindents a
+/// script body.
 #[allow(non_upper_case_globals)]
-pub(crate) const script_: Node = Node::element("script");
+pub const script_: Node = Node::element("script").preformatted();
 
+/// An inline `<style>` element. Use [`Node::rule`] to interpolate runtime data into it safely.
+///
+/// Preformatted by default (see [`Node::preformatted`]): pretty-printing never reindents a
+/// style body.
 #[allow(non_upper_case_globals)]
-pub(crate) const style_: Node = Node::element("style");
+pub const style_: Node = Node::element("style").preformatted();
+
+/// The `pre` HTML element.
+///
+/// Preformatted by default (see [`Node::preformatted`]): pretty-printing never reindents its
+/// contents, since doing so would change the text it renders.
+#[allow(non_upper_case_globals)]
+pub const pre_: Node = Node::element("pre").preformatted();
+
+/// The `textarea` HTML element.
+///
+/// Preformatted by default (see [`Node::preformatted`]): pretty-printing never reindents its
+/// contents, since doing so would change the text it renders.
+#[allow(non_upper_case_globals)]
+pub const textarea_: Node = Node::element("textarea").preformatted();
 
 #[rustfmt::skip]
 impl_tag![
@@ -51,7 +74,7 @@ impl_tag![
     // content sectioning
     address, article, aside, footer, header, h1, h2, h3, h4, h5, h6, main, nav, section,
     // text content
-    blockquote, dd, div, dl, dt, figcaption, figure, hr, li, menu, ol, p, pre, ul,
+    blockquote, dd, div, dl, dt, figcaption, figure, hr, li, menu, ol, p, /* pre, */ ul,
     // inline text semantics
     a, abbr, b, bdi, bdo, br, cite, code, data, dfn, em, i, kbd, mark, q, rp, rt, ruby, s, samp,
     small, span, strong, sub, sup, time, u, var, wbr,
@@ -62,14 +85,14 @@ impl_tag![
     // svg and mathml
     svg, math,
     // scripting
-    canvas, /* script, */ noscript, 
+    canvas, /* script, */ noscript,
     // demarcating edits
     del, ins,
     // table content
     caption, col, colgroup, table, tbody, td, tfoot, th, thead, tr,
     // forms
     button, datalist, fieldset, form, input, label, legend, meter, optgroup, option, output,
-    progress, select, textarea,
+    progress, select, /* textarea, */
     // interactive elements
     details, dialog, summary,
     // web components
@@ -1,5 +1,4 @@
 use crate::encode;
-use fastrand;
 use std::collections::BTreeMap;
 use std::fmt::{Display, Write};
 
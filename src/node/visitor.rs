@@ -1,7 +1,5 @@
-use super::{tag::*, Node};
+use super::Node;
 use std::borrow::Cow;
-use std::collections::btree_map::Entry;
-use std::collections::BTreeSet;
 use std::fmt;
 
 enum Tag<'n> {
@@ -31,24 +29,26 @@ pub trait NodeVisitor {
 // Core traversal code:
 // Visits the nodes in the tree in the order they would appear in html
 //
-// Element nodes nodes are visited twice; for the start & end tags.
-// Text nodes are visited once
-// Fragment nodes are skipped, but the nodes they contain are visited
+// Element nodes are visited twice; for the start & end tags.
+// Text nodes are visited once.
+// Fragment nodes are not visited themselves, but the nodes they contain are.
 pub fn visit_nodes<V: NodeVisitor>(
     start: &mut Node,
     mut visitor: V,
-) -> Result<(), <V as NodeVisitor>::Error> {
-    let mut visit_later: Vec<Tag> = vec![];
-    visit_later.push(Tag::Open(start));
+) -> Result<V, <V as NodeVisitor>::Error> {
+    let mut visit_later: Vec<Tag> = vec![Tag::Open(start)];
 
     while let Some(t) = visit_later.pop() {
         match t {
-            Tag::Open(el) => {
-                if el.tag.is_empty() {
-                    visitor.visit_text(&el.text)?;
-                    continue;
+            Tag::Open(el) if el.is_text() => {
+                visitor.visit_text(&el.text)?;
+            }
+            Tag::Open(el) if el.is_fragment() => {
+                for child in el.children.iter_mut().rev() {
+                    visit_later.push(Tag::Open(child));
                 }
-
+            }
+            Tag::Open(el) => {
                 visitor.visit_open_tag(el)?;
 
                 if el.is_void() {
@@ -70,7 +70,7 @@ pub fn visit_nodes<V: NodeVisitor>(
 
     visitor.finish()?;
 
-    Ok(())
+    Ok(visitor)
 }
 
 // A visitor that transforms a Node tree to an html string
@@ -78,6 +78,17 @@ pub struct HtmlStringWriter<W> {
     html: W,
     indent_level: usize,
     indent: bool,
+    // The total bytes of *text content* that may be written (`None` means unlimited), how many
+    // have been written so far, and the stack of tags currently open, so that if the budget is
+    // exhausted mid-traversal every open tag can still be closed.
+    budget: Option<usize>,
+    written: usize,
+    // Tag name and whether that tag is preformatted, so a close tag knows whether it needs to
+    // leave the preformatted context it opened.
+    open_tags: Vec<(&'static str, bool)>,
+    // How many currently-open tags are preformatted (e.g. `pre`/`script`); indentation and
+    // newlines are suppressed anywhere below the first one.
+    preformatted_depth: usize,
 }
 
 impl<W: fmt::Write> HtmlStringWriter<W> {
@@ -86,9 +97,31 @@ impl<W: fmt::Write> HtmlStringWriter<W> {
             html: inner,
             indent_level: 0,
             indent,
+            budget: None,
+            written: 0,
+            open_tags: Vec::new(),
+            preformatted_depth: 0,
+        }
+    }
+
+    /// Like [`HtmlStringWriter::new`], but stops writing text once `max_len` bytes of text
+    /// content have been emitted, closing every tag still open so the output stays well-formed.
+    pub fn new_truncated(inner: W, indent: bool, max_len: usize) -> Self {
+        Self {
+            html: inner,
+            indent_level: 0,
+            indent,
+            budget: Some(max_len),
+            written: 0,
+            open_tags: Vec::new(),
+            preformatted_depth: 0,
         }
     }
 
+    fn in_preformatted(&self) -> bool {
+        self.preformatted_depth > 0
+    }
+
     fn increment_indent(&mut self) {
         if self.indent {
             self.indent_level += 1;
@@ -102,7 +135,7 @@ impl<W: fmt::Write> HtmlStringWriter<W> {
     }
 
     fn current_indent(&self) -> String {
-        if self.indent {
+        if self.indent && !self.in_preformatted() {
             "  ".repeat(self.indent_level)
         } else {
             String::new()
@@ -110,7 +143,7 @@ impl<W: fmt::Write> HtmlStringWriter<W> {
     }
 
     fn newline(&self) -> &'static str {
-        if self.indent {
+        if self.indent && !self.in_preformatted() {
             "\n"
         } else {
             ""
@@ -118,13 +151,35 @@ impl<W: fmt::Write> HtmlStringWriter<W> {
     }
 
     fn indent_text<'s>(&self, text: &'s str) -> Cow<'s, str> {
-        if !self.indent {
+        if !self.indent || self.in_preformatted() {
             return Cow::Borrowed(text);
         }
 
         let replacement = format!("\n{}", self.current_indent());
         Cow::Owned(text.trim_end().replace('\n', &replacement))
     }
+
+    // `was_preformatted` is whether the element being closed is itself preformatted; the
+    // preformatted context is only left after computing this tag's own (suppressed) indent, so
+    // the closing tag stays tight against its content, while the newline that follows it is free
+    // to resume normal pretty-printing for whatever comes next.
+    fn write_close_tag(&mut self, tag: &'static str, was_preformatted: bool) -> fmt::Result {
+        self.decrement_indent();
+        let indent = self.current_indent();
+        if was_preformatted {
+            self.preformatted_depth -= 1;
+        }
+        write!(self.html, "{}</{}>{}", indent, tag, self.newline())
+    }
+
+    // Closes every tag still on the open stack, in LIFO order, so a truncated render is never
+    // left with dangling open tags.
+    fn close_remaining_tags(&mut self) -> fmt::Result {
+        while let Some((tag, was_preformatted)) = self.open_tags.pop() {
+            self.write_close_tag(tag, was_preformatted)?;
+        }
+        Ok(())
+    }
 }
 
 impl<W: fmt::Write> NodeVisitor for HtmlStringWriter<W> {
@@ -133,26 +188,47 @@ impl<W: fmt::Write> NodeVisitor for HtmlStringWriter<W> {
     fn visit_open_tag(&mut self, el: &mut Node) -> Result<(), Self::Error> {
         write!(self.html, "{}<{}", self.current_indent(), el.tag)?;
         write!(self.html, "{}", el.attributes)?;
+        if !el.variables.is_empty() {
+            write!(self.html, " style=\"{}\"", el.variables)?;
+        }
+        // Enter the preformatted context (if any) before computing the trailing newline, so a
+        // `<pre>`'s own opening tag doesn't get a newline appended that wasn't in the source.
+        if el.preformatted {
+            self.preformatted_depth += 1;
+        }
         write!(self.html, ">{}", self.newline())?;
         if !el.is_void() {
             self.increment_indent();
+            self.open_tags.push((el.tag, el.preformatted));
         }
         Ok(())
     }
 
     fn visit_close_tag(&mut self, tag: &'static str) -> Result<(), Self::Error> {
-        self.decrement_indent();
-        write!(
-            self.html,
-            "{}</{}>{}",
-            self.current_indent(),
-            tag,
-            self.newline()
-        )?;
-        Ok(())
+        let was_preformatted = self.open_tags.pop().is_some_and(|(_, p)| p);
+        self.write_close_tag(tag, was_preformatted)
     }
 
     fn visit_text(&mut self, text: &str) -> Result<(), Self::Error> {
+        if let Some(budget) = self.budget {
+            let remaining = budget.saturating_sub(self.written);
+            if text.len() > remaining {
+                let mut cut = remaining;
+                while cut > 0 && !text.is_char_boundary(cut) {
+                    cut -= 1;
+                }
+                if cut > 0 {
+                    write!(self.html, "{}{}", self.current_indent(), &text[..cut])?;
+                }
+                self.written = budget;
+                self.close_remaining_tags()?;
+                // Signals the caller to stop traversal; writing to a `String` never fails for
+                // any other reason, so this is unambiguous.
+                return Err(fmt::Error);
+            }
+            self.written += text.len();
+        }
+
         let text = self.indent_text(text);
         write!(
             self.html,
@@ -164,3 +240,369 @@ impl<W: fmt::Write> NodeVisitor for HtmlStringWriter<W> {
         Ok(())
     }
 }
+
+// A visitor that renders a tree to an HTML string under a byte budget, like `HtmlStringWriter`'s
+// truncated mode, but keeps open tags "pending" instead of writing them out immediately: an
+// ancestor chain is only flushed once a descendant actually has something to show (text, or a
+// void element), and only that flushed chain is guaranteed to be closed. Only text is charged
+// against the budget; tags never are. A subtree that never produces anything is dropped instead
+// of leaving behind a skeleton of empty tags.
+pub struct LimitedHtmlStringWriter<W> {
+    html: W,
+    budget: usize,
+    written: usize,
+    // Rendered `<tag attrs>` strings not yet written, paired with the tag name so a matching
+    // close can tell whether to just discard it or to pop `open_tags` and write the close tag.
+    pending: Vec<(String, &'static str)>,
+    open_tags: Vec<&'static str>,
+}
+
+impl<W: fmt::Write> LimitedHtmlStringWriter<W> {
+    pub fn new(inner: W, max_bytes: usize) -> Self {
+        Self {
+            html: inner,
+            budget: max_bytes,
+            written: 0,
+            pending: Vec::new(),
+            open_tags: Vec::new(),
+        }
+    }
+
+    fn flush_pending(&mut self) -> fmt::Result {
+        for (rendered, tag) in self.pending.drain(..) {
+            self.html.write_str(&rendered)?;
+            self.open_tags.push(tag);
+        }
+        Ok(())
+    }
+
+    // Closes every tag still on the open stack, in LIFO order, so a truncated render is never
+    // left with dangling open tags.
+    fn close_remaining_tags(&mut self) -> fmt::Result {
+        while let Some(tag) = self.open_tags.pop() {
+            write!(self.html, "</{}>", tag)?;
+        }
+        Ok(())
+    }
+}
+
+// A visitor that renders a tree to word-wrapped plain text, e.g. for a `text/plain` alternative
+// to an HTML email, or terminal output. Loosely modeled on html2text: `p`/`div`/headings/
+// `blockquote` force a paragraph break (a blank line) on their close tag, `li`/`ul`/`ol` force
+// only a line break (list items read better stacked tightly), and everything else (`span`, `a`,
+// `em`, ...) is transparent inline content. Runs of whitespace collapse the way a browser would
+// collapse them. An `a` element renders as its visible text followed by the link target in
+// brackets.
+pub struct PlainTextWriter<W> {
+    out: W,
+    width: usize,
+    lines: Vec<String>,
+    line: String,
+    // `Some(n)` for an open `<ol>` with `n` the next item number, `None` for an open `<ul>`. One
+    // entry per currently nested list.
+    list_stack: Vec<Option<usize>>,
+    // The `href` of the `<a>` element currently open, if any, so its close tag can append it.
+    link_stack: Vec<Option<String>>,
+}
+
+// Tags whose close tag starts a new paragraph (a blank line), as opposed to `li`/`ul`/`ol` which
+// only start a new line: list items read better stacked tightly, with blank-line separation from
+// the surrounding prose coming from whatever paragraph wraps the list instead.
+fn is_paragraph_tag(tag: &str) -> bool {
+    matches!(tag, "p" | "div" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "blockquote")
+}
+
+impl<W: fmt::Write> PlainTextWriter<W> {
+    pub fn new(inner: W, width: usize) -> Self {
+        Self {
+            out: inner,
+            width: width.max(1),
+            lines: Vec::new(),
+            line: String::new(),
+            list_stack: Vec::new(),
+            link_stack: Vec::new(),
+        }
+    }
+
+    fn indent(&self) -> String {
+        "  ".repeat(self.list_stack.len())
+    }
+
+    fn flush_line(&mut self) {
+        if !self.line.is_empty() {
+            self.lines.push(std::mem::take(&mut self.line));
+        }
+    }
+
+    // Ends the current line and ensures whatever comes next starts a new paragraph, without
+    // leaving behind runs of multiple blank lines.
+    fn break_paragraph(&mut self) {
+        self.flush_line();
+        if self.lines.last().is_some_and(|l| !l.is_empty()) {
+            self.lines.push(String::new());
+        }
+    }
+
+    // Appends `word` to the current line, wrapping onto a new one first if it wouldn't fit. A
+    // line that already ends in a space (e.g. right after a `- `/`N. ` list prefix) doesn't get
+    // an extra separator before the word.
+    fn push_word(&mut self, word: &str) {
+        if word.is_empty() {
+            return;
+        }
+
+        let needs_space = !self.line.is_empty() && !self.line.ends_with(' ');
+        let projected = self.line.chars().count() + usize::from(needs_space) + word.chars().count();
+
+        if !self.line.is_empty() && projected > self.width {
+            self.flush_line();
+            self.line.push_str(&self.indent());
+            self.line.push_str(word);
+            return;
+        }
+
+        if needs_space {
+            self.line.push(' ');
+        } else if self.line.is_empty() {
+            self.line.push_str(&self.indent());
+        }
+        self.line.push_str(word);
+    }
+}
+
+impl<W: fmt::Write> NodeVisitor for PlainTextWriter<W> {
+    type Error = fmt::Error;
+
+    fn visit_open_tag(&mut self, el: &mut Node) -> Result<(), Self::Error> {
+        match el.tag {
+            "a" => self.link_stack.push(el.attributes.get("href").map(String::from)),
+            "ul" => {
+                self.flush_line();
+                self.list_stack.push(None);
+            }
+            "ol" => {
+                self.flush_line();
+                self.list_stack.push(Some(1));
+            }
+            "li" => {
+                self.flush_line();
+                let prefix = match self.list_stack.last_mut() {
+                    Some(Some(n)) => {
+                        let prefix = format!("{}. ", n);
+                        *n += 1;
+                        prefix
+                    }
+                    _ => String::from("- "),
+                };
+                let indent = "  ".repeat(self.list_stack.len().saturating_sub(1));
+                self.line.push_str(&indent);
+                self.line.push_str(&prefix);
+            }
+            tag if is_paragraph_tag(tag) => self.break_paragraph(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn visit_close_tag(&mut self, tag: &'static str) -> Result<(), Self::Error> {
+        match tag {
+            "a" => {
+                if let Some(href) = self.link_stack.pop().flatten() {
+                    self.push_word(&format!("[{}]", href));
+                }
+            }
+            "ul" | "ol" => {
+                self.list_stack.pop();
+                self.flush_line();
+            }
+            "li" => self.flush_line(),
+            tag if is_paragraph_tag(tag) => self.break_paragraph(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn visit_text(&mut self, text: &str) -> Result<(), Self::Error> {
+        for word in text.split_whitespace() {
+            self.push_word(word);
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), Self::Error> {
+        self.flush_line();
+        while self.lines.last().is_some_and(|l| l.is_empty()) {
+            self.lines.pop();
+        }
+        self.out.write_str(&self.lines.join("\n"))
+    }
+}
+
+impl<W: fmt::Write> NodeVisitor for LimitedHtmlStringWriter<W> {
+    type Error = fmt::Error;
+
+    fn visit_open_tag(&mut self, el: &mut Node) -> Result<(), Self::Error> {
+        use fmt::Write as _;
+
+        let mut rendered = format!("<{}", el.tag);
+        write!(rendered, "{}", el.attributes)?;
+        if !el.variables.is_empty() {
+            write!(rendered, " style=\"{}\"", el.variables)?;
+        }
+        rendered.push('>');
+
+        if el.is_void() {
+            self.flush_pending()?;
+            return self.html.write_str(&rendered);
+        }
+
+        self.pending.push((rendered, el.tag));
+        Ok(())
+    }
+
+    fn visit_close_tag(&mut self, tag: &'static str) -> Result<(), Self::Error> {
+        if let Some((_, pending_tag)) = self.pending.last() {
+            if *pending_tag == tag {
+                self.pending.pop();
+                return Ok(());
+            }
+        }
+        self.open_tags.pop();
+        write!(self.html, "</{}>", tag)
+    }
+
+    fn visit_text(&mut self, text: &str) -> Result<(), Self::Error> {
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        let remaining = self.budget.saturating_sub(self.written);
+        if text.len() > remaining {
+            let mut cut = remaining;
+            while cut > 0 && !text.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            if cut > 0 {
+                self.flush_pending()?;
+                self.html.write_str(&text[..cut])?;
+            }
+            self.written = self.budget;
+            self.close_remaining_tags()?;
+            // Signals the caller to stop traversal; writing to a `String` never fails for any
+            // other reason, so this is unambiguous.
+            return Err(fmt::Error);
+        }
+
+        self.flush_pending()?;
+        self.html.write_str(text)?;
+        self.written += text.len();
+        Ok(())
+    }
+}
+
+// A visitor that renders a tree to an HTML string capped at `max_bytes` of *total* output (start
+// and end tags and attributes count against the budget, unlike `HtmlStringWriter`'s truncated
+// mode, where only text content does). Once the budget would be exceeded, no further elements are
+// opened and no further text is written, but traversal still runs to completion so that `finish`
+// can pop every tag that was actually opened and close it, in reverse order, regardless of
+// budget — the output is always well-formed HTML, mirroring rustdoc's length-limited writer.
+pub struct CappedHtmlStringWriter<W> {
+    html: W,
+    budget: usize,
+    written: usize,
+    truncated: bool,
+    open_tags: Vec<&'static str>,
+}
+
+impl<W: fmt::Write> CappedHtmlStringWriter<W> {
+    pub fn new(inner: W, max_bytes: usize) -> Self {
+        Self {
+            html: inner,
+            budget: max_bytes,
+            written: 0,
+            truncated: false,
+            open_tags: Vec::new(),
+        }
+    }
+
+    /// Whether the budget was exhausted before the whole tree could be written, so callers can
+    /// append an ellipsis or similar to the output.
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.html.write_str(s)?;
+        self.written += s.len();
+        Ok(())
+    }
+}
+
+impl<W: fmt::Write> NodeVisitor for CappedHtmlStringWriter<W> {
+    type Error = fmt::Error;
+
+    fn visit_open_tag(&mut self, el: &mut Node) -> Result<(), Self::Error> {
+        use fmt::Write as _;
+
+        if self.truncated {
+            return Ok(());
+        }
+
+        let mut rendered = format!("<{}", el.tag);
+        write!(rendered, "{}", el.attributes)?;
+        if !el.variables.is_empty() {
+            write!(rendered, " style=\"{}\"", el.variables)?;
+        }
+        rendered.push('>');
+
+        if rendered.len() > self.budget.saturating_sub(self.written) {
+            self.truncated = true;
+            return Ok(());
+        }
+
+        self.write_str(&rendered)?;
+        if !el.is_void() {
+            self.open_tags.push(el.tag);
+        }
+        Ok(())
+    }
+
+    fn visit_close_tag(&mut self, tag: &'static str) -> Result<(), Self::Error> {
+        // Once truncated, nothing more is written here: tags opened after truncation (including
+        // the element that tipped us over the budget) were never written, and tags that were
+        // written are still on `open_tags` and get closed by `finish`.
+        if self.truncated || self.open_tags.last() != Some(&tag) {
+            return Ok(());
+        }
+        self.open_tags.pop();
+        self.write_str(&format!("</{}>", tag))
+    }
+
+    fn visit_text(&mut self, text: &str) -> Result<(), Self::Error> {
+        if self.truncated || text.is_empty() {
+            return Ok(());
+        }
+
+        let remaining = self.budget.saturating_sub(self.written);
+        if text.len() > remaining {
+            let mut cut = remaining;
+            while cut > 0 && !text.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            if cut > 0 {
+                self.write_str(&text[..cut])?;
+            }
+            self.truncated = true;
+            return Ok(());
+        }
+
+        self.write_str(text)
+    }
+
+    fn finish(&mut self) -> Result<(), Self::Error> {
+        while let Some(tag) = self.open_tags.pop() {
+            write!(self.html, "</{}>", tag)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+//! Parsing an HTML string back into a [`Node`] tree, inverting [`Node::write_to_string`]
+use crate::sanitize::{append_child, current_mut, decode_entities, parse_attributes};
+use crate::tag::custom_;
+use crate::{text, Node};
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+
+const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+// Tags whose end tag is optional in HTML5: opening one implicitly closes a same-named element
+// still open directly above it, e.g. a second `<li>` closes the one before it.
+const IMPLICIT_CLOSE_ON_SIBLING: &[&str] = &["li", "p", "dt", "dd", "tr", "td", "th", "option"];
+
+/// An error encountered while parsing malformed HTML with [`Node::parse`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// An opening tag (e.g. `<div class="x"`) was never closed with a `>`.
+    UnclosedTag,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnclosedTag => write!(f, "unclosed tag"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// `Node`'s tag (and attribute key) fields are `&'static str`, but a parsed tag/attribute name is
+// only known at runtime. Interning it gives out a `&'static str` while deduplicating repeats
+// (e.g. many `<li>`s) so a document doesn't leak one allocation per element.
+//
+// The cache this populates is never freed, so this is only safe to call on a bounded,
+// trusted vocabulary of names; see the caveat on `Node::parse`. A caller parsing
+// attacker-controlled HTML with many distinct names would grow this cache without bound for the
+// life of the process.
+fn intern(name: &str) -> &'static str {
+    static INTERNED: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    let mut cache = INTERNED
+        .get_or_init(|| Mutex::new(HashSet::new()))
+        .lock()
+        .unwrap();
+    if let Some(existing) = cache.get(name) {
+        return existing;
+    }
+    let leaked: &'static str = Box::leak(name.to_string().into_boxed_str());
+    cache.insert(leaked);
+    leaked
+}
+
+pub(crate) fn parse(html: &str) -> Result<Node, ParseError> {
+    let mut root = Node::fragment();
+    let mut stack: Vec<(String, Node)> = Vec::new();
+
+    let len = html.len();
+    let mut i = 0;
+    while i < len {
+        if html.as_bytes()[i] != b'<' {
+            let end = html[i..].find('<').map(|p| i + p).unwrap_or(len);
+            let decoded = decode_entities(&html[i..end]);
+            append_child(current_mut(&mut stack, &mut root), text(decoded));
+            i = end;
+            continue;
+        }
+
+        if let Some(rest) = html[i..].strip_prefix("<!--") {
+            i += 4 + rest.find("-->").map(|p| p + 3).unwrap_or(rest.len());
+            continue;
+        }
+        if html[i..].starts_with("<!") {
+            i += html[i..].find('>').map(|p| p + 1).unwrap_or(len - i);
+            continue;
+        }
+
+        if let Some(rest) = html[i..].strip_prefix("</") {
+            let name_end = rest
+                .find(|c: char| c == '>' || c.is_ascii_whitespace())
+                .unwrap_or(rest.len());
+            let name = rest[..name_end].to_ascii_lowercase();
+            i += 2 + rest.find('>').map(|p| p + 1).unwrap_or(rest.len());
+
+            if let Some(pos) = stack.iter().rposition(|(n, _)| *n == name) {
+                while stack.len() > pos {
+                    let (_, completed) = stack.pop().expect("just checked len > pos");
+                    append_child(current_mut(&mut stack, &mut root), completed);
+                }
+            }
+            continue;
+        }
+
+        let rest = &html[i + 1..];
+        let name_end = rest
+            .find(|c: char| c.is_ascii_whitespace() || c == '>' || c == '/')
+            .unwrap_or(rest.len());
+
+        if !rest[..name_end].starts_with(|c: char| c.is_ascii_alphabetic()) {
+            // Not a real tag (e.g. a stray `<`); treat it as literal text.
+            append_child(current_mut(&mut stack, &mut root), text("<"));
+            i += 1;
+            continue;
+        }
+        let name = rest[..name_end].to_ascii_lowercase();
+
+        if !rest[name_end..].contains('>') {
+            return Err(ParseError::UnclosedTag);
+        }
+        let (attrs, self_closing, tag_len) = parse_attributes(&rest[name_end..]);
+        i += 1 + name_end + tag_len;
+
+        // An opening `<li>`/`<p>`/... implicitly closes a still-open sibling of the same name.
+        if let Some((top_name, _)) = stack.last() {
+            if *top_name == name && IMPLICIT_CLOSE_ON_SIBLING.contains(&name.as_str()) {
+                let (_, completed) = stack.pop().expect("just checked stack.last()");
+                append_child(current_mut(&mut stack, &mut root), completed);
+            }
+        }
+
+        let is_leaf = self_closing || VOID_TAGS.contains(&name.as_str());
+        let node = custom_(intern(&name)).with(
+            attrs
+                .into_iter()
+                .map(|(key, value)| (intern(&key), value, false))
+                .collect::<Vec<_>>(),
+        );
+
+        if is_leaf {
+            append_child(current_mut(&mut stack, &mut root), node);
+        } else {
+            stack.push((name, node));
+        }
+    }
+
+    while let Some((_, completed)) = stack.pop() {
+        append_child(current_mut(&mut stack, &mut root), completed);
+    }
+
+    Ok(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_a_simple_tree() {
+        let mut html = parse(r#"<div class="a"><p>hi</p></div>"#).unwrap();
+        assert_eq!(
+            html.write_to_string(false),
+            r#"<div class="a"><p>hi</p></div>"#
+        );
+    }
+
+    #[test]
+    fn handles_void_elements_without_a_close_tag() {
+        let mut html = parse("<p>a<br>b</p>").unwrap();
+        assert_eq!(html.write_to_string(false), "<p>a<br>b</p>");
+    }
+
+    #[test]
+    fn implicitly_closes_consecutive_list_items() {
+        let mut html = parse("<ul><li>one<li>two</ul>").unwrap();
+        assert_eq!(
+            html.write_to_string(false),
+            "<ul><li>one</li><li>two</li></ul>"
+        );
+    }
+
+    #[test]
+    fn decodes_entities_in_text_and_attributes() {
+        // Attribute values are only re-escaped for `"` (see `encode::attr`), so a decoded `&`
+        // is written back out literally rather than as `&amp;`.
+        let mut html = parse(r#"<p title="Tom &amp; Jerry">Tom &amp; Jerry</p>"#).unwrap();
+        assert_eq!(
+            html.write_to_string(false),
+            r#"<p title="Tom & Jerry">Tom &amp; Jerry</p>"#
+        );
+    }
+
+    #[test]
+    fn rejects_an_unclosed_tag() {
+        assert_eq!(parse("<div class=\"a\"").unwrap_err(), ParseError::UnclosedTag);
+    }
+}
@@ -0,0 +1,421 @@
+//! Sanitizing a string of untrusted HTML into a [`Node`] tree
+use crate::tag::custom_;
+use crate::{text, Node};
+
+// Elements that never have a closing tag
+const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// A configurable allowlist used by [`sanitize`] to decide which elements, attributes and
+/// rewrites survive when turning untrusted HTML into a [`Node`] tree.
+///
+/// Two safety rules are always applied regardless of policy and cannot be turned off: every
+/// attribute whose name starts with `on` (an event handler) is stripped, and any `href`/`src`
+/// using the `javascript:` scheme is dropped.
+///
+/// Start from [`Policy::relaxed`] or [`Policy::strict`] and adjust fields with struct-update
+/// syntax rather than building one from scratch.
+pub struct Policy {
+    /// Lowercased tag names kept in the output. An element outside this set is discarded, but
+    /// its text content (and allowed descendants) is kept in its place.
+    pub allowed_tags: &'static [&'static str],
+    /// Elements dropped entirely, along with everything nested inside them.
+    pub dropped_tags: &'static [&'static str],
+    /// Attribute allowlist, keyed by tag name; the key `"*"` applies to every allowed tag.
+    pub allowed_attributes: &'static [(&'static str, &'static [&'static str])],
+    /// When `true`, an `<img src="...">` has its `src` rewritten to `data-source="..."` instead
+    /// of being kept as-is, so images are not auto-loaded.
+    pub defer_images: bool,
+}
+
+impl Policy {
+    /// A permissive preset covering common prose markup: headings, inline emphasis, links, lists,
+    /// quotes and images. This is the policy [`sanitized_html`] uses.
+    pub fn relaxed() -> Policy {
+        Policy {
+            allowed_tags: &[
+                "a", "b", "blockquote", "br", "code", "em", "h1", "h2", "h3", "h4", "h5", "h6",
+                "hr", "i", "img", "li", "ol", "p", "pre", "span", "strong", "ul",
+            ],
+            dropped_tags: &["script", "style", "iframe", "object"],
+            allowed_attributes: &[(
+                "*",
+                &["alt", "class", "dir", "href", "id", "lang", "rel", "target", "title"],
+            )],
+            defer_images: true,
+        }
+    }
+
+    /// A conservative preset for fully untrusted content (e.g. a comment or chat message): a
+    /// small allowlist of inline/structural tags, no images, and only `href`/`title` kept on
+    /// `<a>`.
+    pub fn strict() -> Policy {
+        Policy {
+            allowed_tags: &["a", "b", "br", "code", "em", "i", "li", "ol", "p", "strong", "ul"],
+            dropped_tags: &["script", "style", "iframe", "object"],
+            allowed_attributes: &[("a", &["href", "title"])],
+            defer_images: true,
+        }
+    }
+}
+
+/// Parses `html` as an untrusted HTML fragment and returns a sanitized [`Node`] tree, filtered
+/// against `policy`, that is safe to embed alongside trusted content (e.g. a newsletter body or a
+/// user comment).
+///
+/// - Elements in `policy.dropped_tags` are removed along with their contents; elements outside
+///   `policy.allowed_tags` (and not dropped) are discarded but their text content is kept.
+/// - Every attribute whose name starts with `on` (an event handler) is removed, as is any
+///   `href`/`src` whose value uses the `javascript:` scheme; these two rules are always applied.
+/// - Surviving attributes are filtered through `policy.allowed_attributes`. When
+///   `policy.defer_images` is set, `<img src="...">` is rewritten to `<img data-source="...">` so
+///   images are not auto-loaded.
+///
+/// Surviving text and attribute values flow through the crate's usual escaping path (`text` and
+/// `Node::with`), so the result is safe to render even though the input was not.
+pub fn sanitize(html: &str, policy: &Policy) -> Node {
+    let mut root = Node::fragment();
+    // Tags still open, paired with the node accumulating their (already sanitized) children.
+    let mut stack: Vec<(String, Node)> = Vec::new();
+    // Set while inside a dropped element: the tag name being skipped, and its nesting depth.
+    let mut skip: Option<(String, usize)> = None;
+
+    let len = html.len();
+    let mut i = 0;
+    while i < len {
+        if html.as_bytes()[i] != b'<' {
+            let end = html[i..].find('<').map(|p| i + p).unwrap_or(len);
+            if skip.is_none() {
+                let decoded = decode_entities(&html[i..end]);
+                append_child(current_mut(&mut stack, &mut root), text(decoded));
+            }
+            i = end;
+            continue;
+        }
+
+        if let Some(rest) = html[i..].strip_prefix("<!--") {
+            i += 4 + rest.find("-->").map(|p| p + 3).unwrap_or(rest.len());
+            continue;
+        }
+        if html[i..].starts_with("<!") {
+            i += html[i..].find('>').map(|p| p + 1).unwrap_or(len - i);
+            continue;
+        }
+
+        if let Some(rest) = html[i..].strip_prefix("</") {
+            let name_end = rest
+                .find(|c: char| c == '>' || c.is_ascii_whitespace())
+                .unwrap_or(rest.len());
+            let name = rest[..name_end].to_ascii_lowercase();
+            i += 2 + rest.find('>').map(|p| p + 1).unwrap_or(rest.len());
+
+            if let Some((skip_name, depth)) = &mut skip {
+                if *skip_name == name {
+                    *depth -= 1;
+                    if *depth == 0 {
+                        skip = None;
+                    }
+                }
+                continue;
+            }
+
+            if let Some(pos) = stack.iter().rposition(|(n, _)| *n == name) {
+                while stack.len() > pos {
+                    let (_, completed) = stack.pop().expect("just checked len > pos");
+                    append_child(current_mut(&mut stack, &mut root), completed);
+                }
+            }
+            continue;
+        }
+
+        let rest = &html[i + 1..];
+        let name_end = rest
+            .find(|c: char| c.is_ascii_whitespace() || c == '>' || c == '/')
+            .unwrap_or(rest.len());
+
+        if !rest[..name_end].starts_with(|c: char| c.is_ascii_alphabetic()) {
+            // Not a real tag (e.g. a stray `<`); treat it as literal text.
+            if skip.is_none() {
+                append_child(current_mut(&mut stack, &mut root), text("<"));
+            }
+            i += 1;
+            continue;
+        }
+        let name = rest[..name_end].to_ascii_lowercase();
+
+        let (attrs, self_closing, tag_len) = parse_attributes(&rest[name_end..]);
+        i += 1 + name_end + tag_len;
+
+        if let Some((skip_name, depth)) = &mut skip {
+            if *skip_name == name && !self_closing {
+                *depth += 1;
+            }
+            continue;
+        }
+
+        if policy.dropped_tags.contains(&name.as_str()) {
+            if !self_closing {
+                skip = Some((name, 1));
+            }
+            continue;
+        }
+
+        let is_leaf = self_closing || VOID_TAGS.contains(&name.as_str());
+
+        let node = match policy.allowed_tags.iter().find(|t| **t == name) {
+            Some(&tag) => custom_(tag).with(sanitized_attributes(&name, attrs, policy)),
+            None => Node::fragment(),
+        };
+
+        if is_leaf {
+            append_child(current_mut(&mut stack, &mut root), node);
+        } else {
+            stack.push((name, node));
+        }
+    }
+
+    // Anything left open when the input ends is closed implicitly.
+    while let Some((_, completed)) = stack.pop() {
+        append_child(current_mut(&mut stack, &mut root), completed);
+    }
+
+    root
+}
+
+/// Parses `input` as an untrusted HTML fragment and returns a sanitized [`Node`] tree, using
+/// [`Policy::relaxed`]. See [`sanitize`] for the filtering rules, or to use a different policy.
+pub fn sanitized_html(input: &str) -> Node {
+    sanitize(input, &Policy::relaxed())
+}
+
+pub(crate) fn current_mut<'a>(stack: &'a mut [(String, Node)], root: &'a mut Node) -> &'a mut Node {
+    stack.last_mut().map(|(_, n)| n).unwrap_or(root)
+}
+
+pub(crate) fn append_child(parent: &mut Node, child: impl Into<Node>) {
+    let owned = std::mem::replace(parent, Node::fragment());
+    *parent = owned.set(std::iter::once(child.into()));
+}
+
+fn sanitized_attributes(
+    tag: &str,
+    attrs: Vec<(String, String)>,
+    policy: &Policy,
+) -> Vec<(&'static str, String, bool)> {
+    let mut kept = Vec::new();
+    for (name, value) in attrs {
+        if name.starts_with("on") {
+            continue;
+        }
+        if (name == "href" || name == "src")
+            && value.trim_start().to_ascii_lowercase().starts_with("javascript:")
+        {
+            continue;
+        }
+        if policy.defer_images && tag == "img" && name == "src" {
+            kept.push(("data-source", value, false));
+            continue;
+        }
+        if let Some(key) = allowed_attribute(policy, tag, &name) {
+            kept.push((key, value, false));
+        }
+    }
+    kept
+}
+
+// Looks up `name` in `policy`'s per-tag attribute allowlist, checking both `tag`'s own entry and
+// the wildcard `"*"` entry that applies to every allowed tag.
+fn allowed_attribute(policy: &Policy, tag: &str, name: &str) -> Option<&'static str> {
+    policy
+        .allowed_attributes
+        .iter()
+        .filter(|(t, _)| *t == tag || *t == "*")
+        .flat_map(|(_, names)| names.iter())
+        .find(|n| **n == name)
+        .copied()
+}
+
+// Parses the attribute list of an opening tag, starting right after the tag name. Returns the
+// parsed (name, decoded value) pairs, whether the tag was self-closed (`/>`), and how many bytes
+// of `input` the tag (attributes + closing `>`) consumed.
+pub(crate) fn parse_attributes(input: &str) -> (Vec<(String, String)>, bool, usize) {
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut attrs = Vec::new();
+    let mut self_closing = false;
+    let mut j = 0;
+
+    loop {
+        while j < len && bytes[j].is_ascii_whitespace() {
+            j += 1;
+        }
+        if j >= len {
+            break;
+        }
+        match bytes[j] {
+            b'>' => {
+                j += 1;
+                break;
+            }
+            b'/' => {
+                self_closing = true;
+                j += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        let name_start = j;
+        while j < len && !matches!(bytes[j], b'=' | b'>' | b'/') && !bytes[j].is_ascii_whitespace() {
+            j += 1;
+        }
+        if j == name_start {
+            j += 1;
+            continue;
+        }
+        let name = input[name_start..j].to_ascii_lowercase();
+
+        while j < len && bytes[j].is_ascii_whitespace() {
+            j += 1;
+        }
+
+        let mut value = String::new();
+        if j < len && bytes[j] == b'=' {
+            j += 1;
+            while j < len && bytes[j].is_ascii_whitespace() {
+                j += 1;
+            }
+            if j < len && (bytes[j] == b'"' || bytes[j] == b'\'') {
+                let quote = bytes[j];
+                j += 1;
+                let value_start = j;
+                while j < len && bytes[j] != quote {
+                    j += 1;
+                }
+                value = decode_entities(&input[value_start..j]);
+                if j < len {
+                    j += 1;
+                }
+            } else {
+                let value_start = j;
+                while j < len && !bytes[j].is_ascii_whitespace() && bytes[j] != b'>' {
+                    j += 1;
+                }
+                value = decode_entities(&input[value_start..j]);
+            }
+        }
+
+        attrs.push((name, value));
+    }
+
+    (attrs, self_closing, j)
+}
+
+// Decodes the handful of named HTML entities, plus numeric (`&#39;`) and hex (`&#x27;`) character
+// references. Unknown or malformed entities are left untouched.
+pub(crate) fn decode_entities(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut i = 0;
+    while i < input.len() {
+        if input.as_bytes()[i] == b'&' {
+            if let Some(end) = input[i..].find(';').map(|p| i + p) {
+                if let Some(decoded) = decode_one_entity(&input[i + 1..end]) {
+                    out.push(decoded);
+                    i = end + 1;
+                    continue;
+                }
+            }
+        }
+        let ch = input[i..].chars().next().expect("i < input.len()");
+        out.push(ch);
+        i += ch.len_utf8();
+    }
+    out
+}
+
+fn decode_one_entity(entity: &str) -> Option<char> {
+    match entity {
+        "amp" => return Some('&'),
+        "lt" => return Some('<'),
+        "gt" => return Some('>'),
+        "quot" => return Some('"'),
+        "apos" => return Some('\''),
+        _ => {}
+    }
+    if let Some(dec) = entity.strip_prefix('#') {
+        if let Some(hex) = dec.strip_prefix('x').or_else(|| dec.strip_prefix('X')) {
+            return u32::from_str_radix(hex, 16).ok().and_then(char::from_u32);
+        }
+        return dec.parse::<u32>().ok().and_then(char::from_u32);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_script_and_style_elements() {
+        let mut html = sanitized_html("<p>hi</p><script>alert(1)</script><style>*{}</style>");
+        assert_eq!(html.write_to_string(false), "<p>hi</p>");
+    }
+
+    #[test]
+    fn strips_event_handler_attributes() {
+        let mut html = sanitized_html(r#"<a href="/ok" onclick="evil()">click</a>"#);
+        assert_eq!(html.write_to_string(false), r#"<a href="/ok">click</a>"#);
+    }
+
+    #[test]
+    fn strips_javascript_scheme_links() {
+        let mut html = sanitized_html(r#"<a href="javascript:evil()">click</a>"#);
+        assert_eq!(html.write_to_string(false), "<a>click</a>");
+    }
+
+    #[test]
+    fn rewrites_image_src_to_data_source() {
+        let mut html = sanitized_html(r#"<img src="https://example.com/cat.png" alt="a cat">"#);
+        assert_eq!(
+            html.write_to_string(false),
+            r#"<img alt="a cat" data-source="https://example.com/cat.png">"#
+        );
+    }
+
+    #[test]
+    fn discards_disallowed_tags_but_keeps_their_text() {
+        let mut html = sanitized_html("<marquee>hello</marquee>");
+        assert_eq!(html.write_to_string(false), "hello");
+    }
+
+    #[test]
+    fn decodes_entities_before_reescaping() {
+        let mut html = sanitized_html("<p>Tom &amp; Jerry</p>");
+        assert_eq!(html.write_to_string(false), "<p>Tom &amp; Jerry</p>");
+    }
+
+    #[test]
+    fn strict_policy_drops_images_and_unlisted_attributes() {
+        let mut html = sanitize(
+            r#"<p class="intro"><img src="x.png"><a href="/ok" class="link">go</a></p>"#,
+            &Policy::strict(),
+        );
+        assert_eq!(html.write_to_string(false), r#"<p><a href="/ok">go</a></p>"#);
+    }
+
+    #[test]
+    fn custom_policy_can_allow_src_without_deferring_images() {
+        let policy = Policy {
+            defer_images: false,
+            allowed_attributes: &[("*", &["src"])],
+            ..Policy::relaxed()
+        };
+        let mut html = sanitize(r#"<img src="https://example.com/cat.png">"#, &policy);
+        assert_eq!(
+            html.write_to_string(false),
+            r#"<img src="https://example.com/cat.png">"#
+        );
+    }
+}